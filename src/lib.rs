@@ -1,3 +1,5 @@
+use std::io::{self, Write};
+
 type Url = String;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -27,6 +29,33 @@ pub enum Line {
     Quote(String),
 }
 
+impl Line {
+    /// Writes this line to `out` in its canonical `text/gemini` form,
+    /// including the trailing newline.
+    pub fn render(&self, out: &mut impl Write) -> io::Result<()> {
+        match self {
+            Line::Text(text) => writeln!(out, "{text}"),
+            Line::Link { url, name } => match name {
+                Some(name) => writeln!(out, "=> {url} {name}"),
+                None => writeln!(out, "=> {url}"),
+            },
+            Line::Heading { level, content } => {
+                writeln!(out, "{} {content}", "#".repeat(*level as usize))
+            }
+            Line::ListItem(text) => writeln!(out, "* {text}"),
+            Line::Quote(text) => writeln!(out, "> {text}"),
+            Line::Pre { alt, content } => {
+                match alt {
+                    Some(alt) => writeln!(out, "```{alt}")?,
+                    None => writeln!(out, "```")?,
+                }
+                writeln!(out, "{content}")?;
+                writeln!(out, "```")
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Page {
     lines: Vec<Line>,
@@ -52,13 +81,18 @@ impl Page {
             }
 
             // Heading.
-            if line.starts_with("#") {
-                let level = line[..3].chars().filter(|&ch| ch == '#').count();
-                let content = line[level..].trim().to_string();
-                lines.push(Line::Heading {
-                    level: level as u8,
-                    content,
-                });
+            if line.starts_with('#') {
+                let mut level = 0u8;
+                let mut content_start = 0;
+                for (i, ch) in line.char_indices().take(3) {
+                    if ch != '#' {
+                        break;
+                    }
+                    level += 1;
+                    content_start = i + ch.len_utf8();
+                }
+                let content = line[content_start..].trim().to_string();
+                lines.push(Line::Heading { level, content });
                 continue;
             }
 
@@ -87,11 +121,16 @@ impl Page {
                     }
                 };
                 let mut content = String::new();
+                let mut first = true;
                 while let Some(line) = raw_lines.next() {
                     if line.starts_with("```") {
                         break;
                     }
+                    if !first {
+                        content.push('\n');
+                    }
                     content.push_str(line);
+                    first = false;
                 }
                 lines.push(Line::Pre { alt, content });
                 continue;
@@ -103,6 +142,218 @@ impl Page {
 
         Self { lines }
     }
+
+    /// Writes the page to `out` as a `text/gemini` byte stream, re-emitting
+    /// every line in its canonical form.
+    pub fn render(&self, out: &mut impl Write) -> io::Result<()> {
+        for line in &self.lines {
+            line.render(out)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for Page {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut buf = Vec::new();
+        self.render(&mut buf).map_err(|_| std::fmt::Error)?;
+        f.write_str(&String::from_utf8_lossy(&buf))
+    }
+}
+
+/// A fluent builder for assembling a [`Page`] line by line, as an
+/// alternative to [`Page::parse`] for code that generates gemtext rather
+/// than reading it.
+#[derive(Debug, Clone, Default)]
+pub struct Builder {
+    lines: Vec<Line>,
+}
+
+impl Builder {
+    pub fn new() -> Self {
+        Self { lines: Vec::new() }
+    }
+
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.lines.push(Line::Text(text.into()));
+        self
+    }
+
+    /// A blank line, equivalent to `text("")`.
+    pub fn blank_line(self) -> Self {
+        self.text(String::new())
+    }
+
+    pub fn link(mut self, url: impl Into<Url>, name: Option<String>) -> Self {
+        self.lines.push(Line::Link {
+            url: url.into(),
+            name,
+        });
+        self
+    }
+
+    pub fn preformatted(mut self, alt: Option<String>, content: impl Into<String>) -> Self {
+        self.lines.push(Line::Pre {
+            alt,
+            content: content.into(),
+        });
+        self
+    }
+
+    pub fn heading(mut self, level: u8, body: impl Into<String>) -> Self {
+        self.lines.push(Line::Heading {
+            level,
+            content: body.into(),
+        });
+        self
+    }
+
+    pub fn list_item(mut self, text: impl Into<String>) -> Self {
+        self.lines.push(Line::ListItem(text.into()));
+        self
+    }
+
+    pub fn quote(mut self, text: impl Into<String>) -> Self {
+        self.lines.push(Line::Quote(text.into()));
+        self
+    }
+
+    pub fn build(self) -> Page {
+        Page { lines: self.lines }
+    }
+}
+
+impl Page {
+    /// Converts the page into semantic HTML, nesting `<section>` elements by
+    /// heading level so the markup mirrors the document's outline.
+    pub fn to_html(&self) -> String {
+        let mut out = String::new();
+        let mut open_sections: Vec<u8> = Vec::new();
+        let mut in_list = false;
+        let mut in_paragraph = false;
+
+        for line in &self.lines {
+            if !matches!(line, Line::ListItem(_)) && in_list {
+                out.push_str("</ul>\n");
+                in_list = false;
+            }
+            if !matches!(line, Line::Text(text) if !text.is_empty()) && in_paragraph {
+                out.push_str("</p>\n");
+                in_paragraph = false;
+            }
+
+            match line {
+                Line::Heading { level, content } => {
+                    while matches!(open_sections.last(), Some(open) if *open >= *level) {
+                        out.push_str("</section>\n");
+                        open_sections.pop();
+                    }
+                    out.push_str("<section>\n");
+                    open_sections.push(*level);
+                    let tag = (*level).clamp(1, 6);
+                    out.push_str(&format!("<h{tag}>{}</h{tag}>\n", escape_html(content)));
+                }
+                Line::Link { url, name } => {
+                    let text = name.as_deref().unwrap_or(url);
+                    out.push_str(&format!(
+                        "<a href=\"{}\">{}</a>\n",
+                        escape_html(url),
+                        escape_html(text)
+                    ));
+                }
+                Line::ListItem(text) => {
+                    if !in_list {
+                        out.push_str("<ul>\n");
+                        in_list = true;
+                    }
+                    out.push_str(&format!("<li>{}</li>\n", escape_html(text)));
+                }
+                Line::Quote(text) => {
+                    out.push_str(&format!("<blockquote>{}</blockquote>\n", escape_html(text)));
+                }
+                Line::Pre { alt, content } => match alt {
+                    Some(alt) => out.push_str(&format!(
+                        "<pre title=\"{0}\" aria-label=\"{0}\">{1}</pre>\n",
+                        escape_html(alt),
+                        escape_html(content)
+                    )),
+                    None => out.push_str(&format!("<pre>{}</pre>\n", escape_html(content))),
+                },
+                Line::Text(text) => {
+                    if !text.is_empty() {
+                        if !in_paragraph {
+                            out.push_str("<p>");
+                            in_paragraph = true;
+                        } else {
+                            out.push(' ');
+                        }
+                        out.push_str(&escape_html(text));
+                    }
+                }
+            }
+        }
+
+        if in_list {
+            out.push_str("</ul>\n");
+        }
+        if in_paragraph {
+            out.push_str("</p>\n");
+        }
+        while open_sections.pop().is_some() {
+            out.push_str("</section>\n");
+        }
+
+        out
+    }
+}
+
+impl Page {
+    /// Resolves every [`Line::Link`]'s URL against `base` following RFC
+    /// 3986, rewriting it in place. Relative references, `../` traversal,
+    /// and scheme-relative links become absolute; already-absolute URLs
+    /// (`gemini://`, `gopher://`, `https://`, ...) are preserved unchanged.
+    pub fn resolve_links(&mut self, base: &url::Url) {
+        for line in &mut self.lines {
+            let Line::Link { url, .. } = line else {
+                continue;
+            };
+            if url::Url::parse(url).is_ok() {
+                continue;
+            }
+            if let Ok(resolved) = base.join(url) {
+                *url = resolved.into();
+            }
+        }
+    }
+
+    /// Like [`Page::resolve_links`], but returns the resolved URLs without
+    /// mutating the page.
+    pub fn resolved_links(&self, base: &url::Url) -> Vec<url::Url> {
+        self.lines
+            .iter()
+            .filter_map(|line| match line {
+                Line::Link { url, .. } => base.join(url).ok(),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Escapes `<`, `>`, `&`, and quote characters for safe inclusion in HTML
+/// text content and attribute values.
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '&' => escaped.push_str("&amp;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
 }
 
 #[cfg(test)]
@@ -190,4 +441,253 @@ They come right after each other.";
 
         assert_eq!(page, expected)
     }
+
+    #[test]
+    fn render_roundtrip() {
+        let page = Page {
+            lines: vec![
+                Line::Heading {
+                    level: 2,
+                    content: "Title".to_string(),
+                },
+                Line::Text("A paragraph.".to_string()),
+                Line::Link {
+                    url: "gemini://example.org/".to_string(),
+                    name: Some("Example".to_string()),
+                },
+                Line::Link {
+                    url: "gemini://example.org/bare".to_string(),
+                    name: None,
+                },
+                Line::ListItem("An item".to_string()),
+                Line::Quote("A quote".to_string()),
+                Line::Pre {
+                    alt: Some("rust".to_string()),
+                    content: "fn main() {}\nprintln!(\"hi\");".to_string(),
+                },
+            ],
+        };
+
+        let rendered = page.to_string();
+        let reparsed = Page::parse(&rendered);
+
+        assert_eq!(reparsed, page);
+    }
+
+    #[test]
+    fn pre_preserves_interior_newlines() {
+        let text = "```\nfirst\nsecond\n\nfourth\n```";
+        let page = Page::parse(text);
+
+        let expected = Page {
+            lines: vec![Line::Pre {
+                alt: None,
+                content: "first\nsecond\n\nfourth".to_string(),
+            }],
+        };
+
+        assert_eq!(page, expected);
+    }
+
+    #[test]
+    fn builder() {
+        let page = Builder::new()
+            .heading(1, "Hi")
+            .link("gemini://x/", Some("X".to_string()))
+            .build();
+
+        let expected = Page {
+            lines: vec![
+                Line::Heading {
+                    level: 1,
+                    content: "Hi".to_string(),
+                },
+                Line::Link {
+                    url: "gemini://x/".to_string(),
+                    name: Some("X".to_string()),
+                },
+            ],
+        };
+
+        assert_eq!(page, expected);
+        assert_eq!(page.to_string(), "# Hi\n=> gemini://x/ X\n");
+    }
+
+    #[test]
+    fn to_html_nests_sections_by_heading_level() {
+        let page = Builder::new()
+            .heading(1, "Top")
+            .text("Intro.")
+            .heading(2, "Sub")
+            .list_item("one")
+            .list_item("two")
+            .heading(2, "Sub 2")
+            .quote("A <quote>")
+            .build();
+
+        let html = page.to_html();
+
+        assert_eq!(
+            html,
+            "<section>\n\
+             <h1>Top</h1>\n\
+             <p>Intro.</p>\n\
+             <section>\n\
+             <h2>Sub</h2>\n\
+             <ul>\n\
+             <li>one</li>\n\
+             <li>two</li>\n\
+             </ul>\n\
+             </section>\n\
+             <section>\n\
+             <h2>Sub 2</h2>\n\
+             <blockquote>A &lt;quote&gt;</blockquote>\n\
+             </section>\n\
+             </section>\n"
+        );
+    }
+
+    #[test]
+    fn to_html_escapes_link_and_pre() {
+        let page = Builder::new()
+            .link("gemini://x/?a=1&b=2", Some("A & B".to_string()))
+            .preformatted(Some("\"quoted\" alt".to_string()), "<tag>".to_string())
+            .build();
+
+        let html = page.to_html();
+
+        assert_eq!(
+            html,
+            "<a href=\"gemini://x/?a=1&amp;b=2\">A &amp; B</a>\n\
+             <pre title=\"&quot;quoted&quot; alt\" aria-label=\"&quot;quoted&quot; alt\">&lt;tag&gt;</pre>\n"
+        );
+    }
+
+    #[test]
+    fn resolve_links_rewrites_relative_urls() {
+        let base = url::Url::parse("gemini://example.org/dir/page.gmi").unwrap();
+        let mut page = Page {
+            lines: vec![
+                Line::Link {
+                    url: "foo/bar/baz.txt".to_string(),
+                    name: None,
+                },
+                Line::Link {
+                    url: "../up.gmi".to_string(),
+                    name: None,
+                },
+                Line::Link {
+                    url: "gemini://other.example/abs".to_string(),
+                    name: None,
+                },
+            ],
+        };
+
+        page.resolve_links(&base);
+
+        let expected = Page {
+            lines: vec![
+                Line::Link {
+                    url: "gemini://example.org/dir/foo/bar/baz.txt".to_string(),
+                    name: None,
+                },
+                Line::Link {
+                    url: "gemini://example.org/up.gmi".to_string(),
+                    name: None,
+                },
+                Line::Link {
+                    url: "gemini://other.example/abs".to_string(),
+                    name: None,
+                },
+            ],
+        };
+
+        assert_eq!(page, expected);
+    }
+
+    #[test]
+    fn resolved_links_does_not_mutate() {
+        let base = url::Url::parse("gemini://example.org/dir/page.gmi").unwrap();
+        let page = Page {
+            lines: vec![Line::Link {
+                url: "foo.gmi".to_string(),
+                name: None,
+            }],
+        };
+
+        let resolved = page.resolved_links(&base);
+
+        assert_eq!(
+            resolved,
+            vec![url::Url::parse("gemini://example.org/dir/foo.gmi").unwrap()]
+        );
+        assert_eq!(
+            page.lines[0],
+            Line::Link {
+                url: "foo.gmi".to_string(),
+                name: None,
+            }
+        );
+    }
+
+    #[test]
+    fn heading_classification_does_not_panic_on_short_lines() {
+        let page = Page::parse("#");
+        assert_eq!(
+            page,
+            Page {
+                lines: vec![Line::Heading {
+                    level: 1,
+                    content: String::new(),
+                }]
+            }
+        );
+
+        let page = Page::parse("##");
+        assert_eq!(
+            page,
+            Page {
+                lines: vec![Line::Heading {
+                    level: 2,
+                    content: String::new(),
+                }]
+            }
+        );
+    }
+
+    #[test]
+    fn heading_classification_does_not_panic_on_multibyte_content() {
+        let page = Page::parse("### 日本語見出し");
+        assert_eq!(
+            page,
+            Page {
+                lines: vec![Line::Heading {
+                    level: 3,
+                    content: "日本語見出し".to_string(),
+                }]
+            }
+        );
+
+        let page = Page::parse("#日本語");
+        assert_eq!(
+            page,
+            Page {
+                lines: vec![Line::Heading {
+                    level: 1,
+                    content: "日本語".to_string(),
+                }]
+            }
+        );
+    }
+
+    #[test]
+    fn quote_classification_does_not_panic_on_multibyte_content() {
+        let page = Page::parse(">日本語");
+        assert_eq!(
+            page,
+            Page {
+                lines: vec![Line::Quote("日本語".to_string())]
+            }
+        );
+    }
 }